@@ -1,46 +1,107 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, BorshSerialize, BorshDeserialize, Debug)]
 struct Rectangle {
     width: u32,
     height: u32
 }
 
+#[derive(Serialize, Deserialize, BorshSerialize, BorshDeserialize, Debug)]
 struct Square {
     side: u32
 }
 
-impl Rectangle {
+// Shared abstraction over shapes so a mixed collection can be held and
+// queried without knowing the concrete type of each one. `area`,
+// `perimeter` and shape identity (`name`/`whoami`) all lived as separate
+// inherent methods on each struct before; now they're unified here.
+trait Shape {
+    fn area(&self) -> u32;
+    fn perimeter(&self) -> u32;
+    fn name(&self) -> &'static str;
 
-    fn whoami() {
-        println!("I am a rectangle");
+    fn whoami(&self) {
+        println!("I am a {}", self.name());
     }
+}
 
+impl Shape for Rectangle {
     fn area(&self) -> u32 {
-        return self.width * self.height
+        self.width * self.height
     }
 
     fn perimeter(&self) -> u32 {
-        return 2 * (self.width + self.height)
+        2 * (self.width + self.height)
     }
-}
-
-impl Square {
 
-    fn whoami() {
-        println!("I am a square");
+    fn name(&self) -> &'static str {
+        "rectangle"
     }
+}
 
+impl Shape for Square {
     fn area(&self) -> u32 {
-        return self.side * self.side
+        self.side * self.side
     }
 
     fn perimeter(&self) -> u32 {
-        return 4 * self.side
+        4 * self.side
+    }
+
+    fn name(&self) -> &'static str {
+        "square"
     }
 }
 
+// Trait objects can't derive serde/borsh directly (there's no single
+// concrete type to (de)serialize into), so `AnyShape` is the enum-tagged
+// stand-in: it can round-trip through the same serialization examples
+// used elsewhere in the crate, and converts to `Box<dyn Shape>` for the
+// dynamic-dispatch geometry subsystem below.
+#[derive(Serialize, Deserialize, BorshSerialize, BorshDeserialize, Debug)]
+enum AnyShape {
+    Rectangle(Rectangle),
+    Square(Square),
+}
+
+impl AnyShape {
+    fn into_box(self) -> Box<dyn Shape> {
+        match self {
+            AnyShape::Rectangle(rectangle) => Box::new(rectangle),
+            AnyShape::Square(square) => Box::new(square),
+        }
+    }
+}
+
+// Aggregates a heterogeneous collection of shapes behind `Box<dyn Shape>`.
+struct Geometry {
+    shapes: Vec<Box<dyn Shape>>,
+}
+
+impl Geometry {
+    fn new(shapes: Vec<Box<dyn Shape>>) -> Self {
+        Geometry { shapes }
+    }
+
+    fn total_area(&self) -> u32 {
+        self.shapes.iter().map(|shape| shape.area()).sum()
+    }
 
+    fn total_perimeter(&self) -> u32 {
+        self.shapes.iter().map(|shape| shape.perimeter()).sum()
+    }
+
+    fn largest_by_area(&self) -> Option<&dyn Shape> {
+        self.shapes
+            .iter()
+            .max_by_key(|shape| shape.area())
+            .map(|shape| shape.as_ref())
+    }
+}
 
 fn main() {
-    
+
     let rect1 = Rectangle {
         width: 10,
         height: 20
@@ -50,11 +111,47 @@ fn main() {
         side: 10
     };
 
-    Rectangle::whoami();
+    rect1.whoami();
     println!("the area of the rectangle is {}", rect1.area());
     println!("the perimeter of the rectangle is {}", rect1.perimeter());
 
-    Square::whoami();
+    square1.whoami();
     println!("the area of the square is {}", square1.area());
     println!("the perimeter of the square is {}", square1.perimeter());
+
+    // Geometry subsystem: a mixed collection held behind `Box<dyn Shape>`.
+    let any_shapes = vec![
+        AnyShape::Rectangle(Rectangle { width: 10, height: 20 }),
+        AnyShape::Square(Square { side: 10 }),
+        AnyShape::Rectangle(Rectangle { width: 3, height: 4 }),
+    ];
+
+    let geometry = Geometry::new(
+        any_shapes
+            .into_iter()
+            .map(AnyShape::into_box)
+            .collect(),
+    );
+
+    println!("total area: {}", geometry.total_area());
+    println!("total perimeter: {}", geometry.total_perimeter());
+    if let Some(largest) = geometry.largest_by_area() {
+        println!(
+            "largest shape by area: {} (area {})",
+            largest.name(),
+            largest.area()
+        );
+    }
+
+    // AnyShape round-trips through the same serde/borsh codecs used by
+    // the other examples in the crate.
+    let tagged = AnyShape::Rectangle(Rectangle { width: 5, height: 6 });
+    let json = serde_json::to_string(&tagged).unwrap();
+    println!("AnyShape JSON: {}", json);
+    let from_json: AnyShape = serde_json::from_str(&json).unwrap();
+    println!("AnyShape from JSON: {:?}", from_json);
+
+    let bytes = borsh::to_vec(&tagged).unwrap();
+    let from_bytes: AnyShape = borsh::from_slice(&bytes).unwrap();
+    println!("AnyShape from borsh bytes: {:?}", from_bytes);
 }
@@ -29,6 +29,49 @@ enum Status {
     Pending,
 }
 
+// Versioned envelope: pins a `format_version` on serialized payloads so an
+// older payload can be migrated forward instead of failing to parse when
+// the schema changes (here, `Person`'s `name` field became `full_name`).
+const FORMAT_VERSION: u32 = 2;
+
+type Migration = fn(serde_json::Value) -> serde_json::Value;
+
+fn migrate_person_v1_to_v2(mut value: serde_json::Value) -> serde_json::Value {
+    if let Some(obj) = value.as_object_mut() {
+        if let Some(name) = obj.remove("name") {
+            obj.insert("full_name".to_string(), name);
+        }
+    }
+    value
+}
+
+const PERSON_MIGRATIONS: &[(u32, Migration)] = &[(1, migrate_person_v1_to_v2)];
+
+fn to_string_versioned<T: Serialize>(data: &T) -> Result<String, serde_json::Error> {
+    serde_json::to_string(&serde_json::json!({
+        "format_version": FORMAT_VERSION,
+        "data": data,
+    }))
+}
+
+fn from_str_versioned<T: for<'de> Deserialize<'de>>(
+    json: &str,
+    migrations: &[(u32, Migration)],
+) -> Result<T, serde_json::Error> {
+    let envelope: serde_json::Value = serde_json::from_str(json)?;
+    let mut version = envelope["format_version"].as_u64().unwrap_or(1) as u32;
+    let mut data = envelope["data"].clone();
+
+    for (from_version, migrate) in migrations {
+        if version == *from_version {
+            data = migrate(data);
+            version += 1;
+        }
+    }
+
+    serde_json::from_value(data)
+}
+
 fn main() -> Result<(), serde_json::Error> {
     // Basic usage
     let user = User {
@@ -54,7 +97,21 @@ fn main() -> Result<(), serde_json::Error> {
     println!("Person JSON: {}", json);
     // Output: {"full_name":"Bob"} 
     // Note: email is None so skipped, password is always skipped
-    
+
+    // Versioned envelope: a v1 payload (old "name" key) upgrades to the
+    // current Person shape via the registered migration.
+    let v1_payload = serde_json::json!({
+        "format_version": 1,
+        "data": { "name": "Grace" },
+    })
+    .to_string();
+
+    let upgraded: Person = from_str_versioned(&v1_payload, PERSON_MIGRATIONS)?;
+    println!("Upgraded from v1 payload: {:?}", upgraded);
+
+    let versioned_json = to_string_versioned(&person)?;
+    println!("Current versioned payload: {}", versioned_json);
+
     let status = Status::Active;
     let status_json = serde_json::to_string(&status)?;
     println!("Status: {}", status_json);
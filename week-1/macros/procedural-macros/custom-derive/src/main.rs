@@ -1,4 +1,7 @@
 use borsh::{BorshSerialize, BorshDeserialize, to_vec, from_slice};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
 
 // Example 1: BorshSerialize and BorshDeserialize
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
@@ -37,6 +40,184 @@ struct Product {
     price: f64,
 }
 
+// Example 6: reference-preserving serialization for shared `Rc` graphs
+//
+// Plain (de)serialization walks a struct as a tree, so two fields that
+// share the same `Rc` allocation come back as two independent copies, and
+// a cycle would recurse forever. `SerializeState`/`DeserializeState` thread
+// a node map (`SerializeNodeMap`/`DeserializeNodeMap`) through the walk
+// (mirroring gluon's traits of the same name)
+// so a shared node is written once, tagged with an id, and every later
+// encounter is just a `{ "ref": id }` pointer back to it.
+type SerializeNodeMap = HashMap<usize, u32>;
+type DeserializeNodeMap<T> = HashMap<u32, Rc<T>>;
+
+trait SerializeState {
+    fn serialize_state(&self, map: &mut SerializeNodeMap) -> serde_json::Value;
+}
+
+// Generic over the shared node type `T`, so any `Rc<T>` graph can
+// hand-implement it (`SharedNode` below is just the first instance) rather
+// than this being a method bolted onto one concrete type.
+trait DeserializeState<T>: Sized {
+    fn deserialize_state(value: &serde_json::Value, map: &mut DeserializeNodeMap<T>) -> Self;
+}
+
+#[derive(Debug)]
+struct SharedNode {
+    name: String,
+    children: Vec<Rc<RefCell<SharedNode>>>,
+}
+
+impl SerializeState for Rc<RefCell<SharedNode>> {
+    fn serialize_state(&self, map: &mut SerializeNodeMap) -> serde_json::Value {
+        let addr = Rc::as_ptr(self) as usize;
+        if let Some(&id) = map.get(&addr) {
+            return serde_json::json!({ "ref": id });
+        }
+
+        // Reserve the id before recursing so a node that reaches itself
+        // through a child sees the `ref` form instead of looping forever.
+        let id = map.len() as u32;
+        map.insert(addr, id);
+
+        let node = self.borrow();
+        let children: Vec<serde_json::Value> = node
+            .children
+            .iter()
+            .map(|child| child.serialize_state(map))
+            .collect();
+
+        serde_json::json!({
+            "id": id,
+            "value": { "name": node.name, "children": children },
+        })
+    }
+}
+
+impl DeserializeState<RefCell<SharedNode>> for Rc<RefCell<SharedNode>> {
+    fn deserialize_state(value: &serde_json::Value, map: &mut DeserializeNodeMap<RefCell<SharedNode>>) -> Self {
+        if let Some(id) = value.get("ref").and_then(|v| v.as_u64()) {
+            return map
+                .get(&(id as u32))
+                .expect("ref to a node id that hasn't been seen yet")
+                .clone();
+        }
+
+        let id = value["id"].as_u64().expect("tagged node missing id") as u32;
+
+        // Allocate and register the node before recursing into its
+        // children, so a child that refs back to this node resolves to
+        // the real `Rc` instead of recursing into deserialize_state again.
+        let node = Rc::new(RefCell::new(SharedNode {
+            name: String::new(),
+            children: Vec::new(),
+        }));
+        map.insert(id, node.clone());
+
+        let contents = &value["value"];
+        let name = contents["name"].as_str().unwrap_or_default().to_string();
+        let children = contents["children"]
+            .as_array()
+            .map(|arr| {
+                arr.iter()
+                    .map(|child| Rc::<RefCell<SharedNode>>::deserialize_state(child, map))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        *node.borrow_mut() = SharedNode { name, children };
+        node
+    }
+}
+
+// Example 7: `DeepClone` vs derived `Clone`
+//
+// A derived `Clone` on a struct holding `Rc`/`Arc` only bumps the
+// refcount, so `clone()` of such a struct still points at the same
+// allocation. `DeepClone` instead walks down and allocates fresh storage
+// at every shared pointer, so the clone is a fully independent graph.
+trait DeepClone {
+    fn deep_clone(&self) -> Self;
+
+    fn deep_clone_from(&mut self, src: &Self)
+    where
+        Self: Sized,
+    {
+        *self = src.deep_clone();
+    }
+}
+
+macro_rules! impl_deep_clone_via_clone {
+    ($($t:ty),* $(,)?) => {
+        $(
+            impl DeepClone for $t {
+                fn deep_clone(&self) -> Self {
+                    self.clone()
+                }
+            }
+        )*
+    };
+}
+
+impl_deep_clone_via_clone!(bool, char, String, i32, u32, i64, u64, f32, f64);
+
+impl<T: DeepClone> DeepClone for Vec<T> {
+    fn deep_clone(&self) -> Self {
+        self.iter().map(DeepClone::deep_clone).collect()
+    }
+}
+
+impl<T: DeepClone> DeepClone for Rc<T> {
+    fn deep_clone(&self) -> Self {
+        Rc::new((**self).deep_clone())
+    }
+}
+
+impl<T: DeepClone> DeepClone for RefCell<T> {
+    fn deep_clone(&self) -> Self {
+        RefCell::new(self.borrow().deep_clone())
+    }
+}
+
+impl DeepClone for Person {
+    fn deep_clone(&self) -> Self {
+        Person {
+            name: self.name.deep_clone(),
+            age: self.age,
+        }
+    }
+}
+
+// Nests an `Rc<Person>` so `clone()` (shares the allocation) and
+// `deep_clone()` (allocates a brand-new `Person`) visibly diverge.
+#[derive(Debug, Clone)]
+struct Team {
+    name: String,
+    lead: Rc<Person>,
+}
+
+impl DeepClone for Team {
+    fn deep_clone(&self) -> Self {
+        Team {
+            name: self.name.deep_clone(),
+            lead: self.lead.deep_clone(),
+        }
+    }
+}
+
+fn to_vec_stateful(root: &Rc<RefCell<SharedNode>>) -> Vec<u8> {
+    let mut map = SerializeNodeMap::new();
+    let value = root.serialize_state(&mut map);
+    serde_json::to_vec(&value).expect("SharedNode graph always serializes to valid JSON")
+}
+
+fn from_slice_stateful(bytes: &[u8]) -> Rc<RefCell<SharedNode>> {
+    let value: serde_json::Value = serde_json::from_slice(bytes).expect("valid stateful payload");
+    let mut map = DeserializeNodeMap::new();
+    Rc::<RefCell<SharedNode>>::deserialize_state(&value, &mut map)
+}
+
 fn main() {
     println!("=== Custom Derive Macros Examples ===\n");
     
@@ -112,7 +293,54 @@ fn main() {
     println!("Product 1 == Product 2: {}", product1 == product2);
     println!("Product 1 == Product 3: {}", product1 == product3);
     println!();
-    
+
+    // Example 6: reference-preserving (stateful) serialization
+    println!("6. Stateful serialization of a shared Rc graph:");
+    let leaf = Rc::new(RefCell::new(SharedNode {
+        name: "leaf".to_string(),
+        children: vec![],
+    }));
+    let root = Rc::new(RefCell::new(SharedNode {
+        name: "root".to_string(),
+        children: vec![leaf.clone(), leaf.clone()], // same leaf, shared twice
+    }));
+
+    let bytes = to_vec_stateful(&root);
+    println!(
+        "Stateful JSON (leaf only appears once): {}",
+        String::from_utf8_lossy(&bytes)
+    );
+
+    let round_tripped = from_slice_stateful(&bytes);
+    let children = &round_tripped.borrow().children;
+    println!(
+        "Round-tripped children share one allocation: {}",
+        Rc::ptr_eq(&children[0], &children[1])
+    );
+    println!();
+
+    // Example 7: DeepClone vs derived Clone
+    println!("7. DeepClone vs derived Clone:");
+    let team1 = Team {
+        name: "Backend".to_string(),
+        lead: Rc::new(Person {
+            name: "Erin".to_string(),
+            age: 40,
+        }),
+    };
+
+    let shallow = team1.clone();
+    let deep = team1.deep_clone();
+    println!(
+        "team1.lead and shallow.lead share an allocation: {}",
+        Rc::ptr_eq(&team1.lead, &shallow.lead)
+    );
+    println!(
+        "team1.lead and deep.lead share an allocation: {}",
+        Rc::ptr_eq(&team1.lead, &deep.lead)
+    );
+    println!();
+
     // Demonstrating the difference between Copy and Clone
     println!("=== Copy vs Clone Demonstration ===");
     demonstrate_copy_vs_clone();